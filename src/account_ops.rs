@@ -0,0 +1,103 @@
+//! Account and transaction-request builders shared between the native demo
+//! (`main.rs`, driving a `GrpcClient`) and the wasm bindings (`wasm.rs`,
+//! driving a `WebTonicRpcClient`).
+//!
+//! These are intentionally client-agnostic: building an `Account` or a
+//! `TransactionRequest` never touches the `Client` itself, only an RNG and
+//! plain values, so both front ends can share the exact same construction
+//! logic instead of maintaining two copies that drift apart. Submitting the
+//! resulting request (`client.submit_new_transaction(...)`) stays inline at
+//! each call site, since that's a one-line call on the concrete `Client<R>`.
+
+use miden_client::account::component::{BasicFungibleFaucet, BasicWallet};
+use miden_client::account::{Account, AccountId};
+use miden_client::auth::AuthSecretKey;
+use miden_client::note::{Note, NoteAttachment, NoteType, create_p2id_note};
+use miden_client::transaction::{OutputNote, TransactionRequest, TransactionRequestBuilder};
+use miden_protocol::Felt;
+use miden_protocol::account::{AccountBuilder, AccountStorageMode, AccountType};
+use miden_protocol::asset::{FungibleAsset, TokenSymbol};
+use rand::RngCore;
+
+use crate::CliError;
+use crate::mnemonic::auth_component_for;
+
+/// Builds a basic wallet account for `init_seed`/`key_pair`.
+pub fn build_wallet_account(init_seed: [u8; 32], key_pair: &AuthSecretKey) -> Result<Account, CliError> {
+    AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountUpdatableCode)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(auth_component_for(key_pair))
+        .with_component(BasicWallet)
+        .build()
+        .map_err(|err| CliError::InitializationError(err.to_string()))
+}
+
+/// Builds a basic fungible faucet account for `init_seed`/`key_pair`.
+pub fn build_faucet_account(
+    init_seed: [u8; 32],
+    key_pair: &AuthSecretKey,
+    symbol: &str,
+    decimals: u8,
+    max_supply: u64,
+) -> Result<Account, CliError> {
+    let symbol = TokenSymbol::new(symbol)
+        .map_err(|err| CliError::InitializationError(format!("invalid token symbol: {err}")))?;
+    let faucet_component = BasicFungibleFaucet::new(symbol, decimals, Felt::new(max_supply))
+        .map_err(|err| CliError::InitializationError(err.to_string()))?;
+
+    AccountBuilder::new(init_seed)
+        .account_type(AccountType::FungibleFaucet)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(auth_component_for(key_pair))
+        .with_component(faucet_component)
+        .build()
+        .map_err(|err| CliError::InitializationError(err.to_string()))
+}
+
+/// Builds a request minting `amount` tokens of `faucet_id` to `recipient_id`.
+pub fn build_mint_request(
+    faucet_id: AccountId,
+    recipient_id: AccountId,
+    amount: u64,
+    rng: &mut impl RngCore,
+) -> Result<TransactionRequest, CliError> {
+    let fungible_asset = FungibleAsset::new(faucet_id, amount)
+        .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+    TransactionRequestBuilder::new()
+        .build_mint_fungible_asset(fungible_asset, recipient_id, NoteType::Public, rng)
+        .map_err(|err| CliError::NoteCreationError(err.to_string()))
+}
+
+/// Builds a request consuming every note in `notes`.
+pub fn build_consume_notes_request(notes: Vec<Note>) -> Result<TransactionRequest, CliError> {
+    TransactionRequestBuilder::new()
+        .build_consume_notes(notes)
+        .map_err(|err| CliError::TransactionError(err.to_string()))
+}
+
+/// Builds a request sending `amount` tokens of `faucet_id` from `sender_id`
+/// to `recipient_id` via a single P2ID note.
+pub fn build_send_p2id_request(
+    sender_id: AccountId,
+    recipient_id: AccountId,
+    faucet_id: AccountId,
+    amount: u64,
+    rng: &mut impl RngCore,
+) -> Result<TransactionRequest, CliError> {
+    let fungible_asset = FungibleAsset::new(faucet_id, amount)
+        .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+    let p2id_note = create_p2id_note(
+        sender_id,
+        recipient_id,
+        vec![fungible_asset.into()],
+        NoteType::Public,
+        NoteAttachment::default(),
+        rng,
+    )?;
+
+    TransactionRequestBuilder::new()
+        .own_output_notes(vec![OutputNote::Full(p2id_note)])
+        .build()
+        .map_err(|err| CliError::TransactionError(err.to_string()))
+}
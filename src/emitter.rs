@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use miden_client::Client;
+use miden_client::account::{Account, AccountId};
+use miden_client::builder::ClientBuilder;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::note::{Note, NoteId};
+use miden_client::rpc::GrpcClient;
+use miden_client_sqlite_store::ClientBuilderSqliteExt;
+use rand::RngCore;
+
+use crate::CliError;
+
+/// Maximum number of `sync_state` polls a worker spends waiting for its
+/// submitted transactions to confirm once the run's duration has elapsed.
+const MAX_CONFIRMATION_POLLS: u32 = 10;
+
+/// Parameters controlling a single load-generation run.
+pub struct EmitterConfig {
+    /// Number of worker accounts submitting transactions concurrently.
+    pub num_workers: usize,
+    /// Target aggregate submissions per second across all workers.
+    pub target_tps: f64,
+    /// How long to run the emitter for.
+    pub duration: Duration,
+    /// Amount pre-funded to each worker from the faucet before the run starts.
+    pub funding_amount: u64,
+    /// RPC connection shared by every worker's own `Client`.
+    pub rpc_client: Arc<GrpcClient>,
+    /// Directory holding each worker's dedicated sqlite store file.
+    pub store_dir: PathBuf,
+}
+
+/// Per-worker tallies collected after its submission loop and confirmation
+/// wait both finish.
+struct WorkerStats {
+    submitted: u64,
+    succeeded: u64,
+    confirmed: u64,
+    submit_latencies: Vec<Duration>,
+    confirm_latencies: Vec<Duration>,
+}
+
+/// Aggregate throughput and latency figures for a completed run.
+#[derive(Debug)]
+pub struct EmitterReport {
+    pub submitted: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    pub confirmed: u64,
+    pub effective_tps: f64,
+    pub submit_p50_latency: Duration,
+    pub submit_p95_latency: Duration,
+    pub submit_p99_latency: Duration,
+    pub confirm_p50_latency: Duration,
+    pub confirm_p95_latency: Duration,
+    pub confirm_p99_latency: Duration,
+}
+
+/// Drives `config.num_workers` independent workers at a configurable target
+/// rate to benchmark a node.
+///
+/// Pre-funds each worker account from `faucet_account` using `client`, then
+/// gives every worker its own `Client` (own sqlite store, same RPC
+/// connection and keystore) so submissions genuinely run concurrently rather
+/// than serializing on a single connection. Each worker builds
+/// mint-to-self transactions and calls `submit_new_transaction` in a loop
+/// paced by a `tokio::time::interval`, then polls `sync_state` once its
+/// window closes to measure how many of its submissions actually confirmed.
+pub async fn run_emitter(
+    client: &mut Client<GrpcClient>,
+    keystore: &Arc<FilesystemKeyStore>,
+    faucet_account: &Account,
+    config: EmitterConfig,
+) -> Result<EmitterReport, CliError> {
+    let mut worker_accounts = Vec::with_capacity(config.num_workers);
+    for _ in 0..config.num_workers {
+        worker_accounts.push(create_worker_account(client, keystore).await?);
+    }
+
+    println!(
+        "Pre-funding {} worker account(s) with {} tokens each...",
+        worker_accounts.len(),
+        config.funding_amount
+    );
+    for worker_account in &worker_accounts {
+        let transaction_request = crate::account_ops::build_mint_request(
+            faucet_account.id(),
+            worker_account.id(),
+            config.funding_amount,
+            client.rng(),
+        )?;
+        client
+            .submit_new_transaction(faucet_account.id(), transaction_request)
+            .await?;
+    }
+    client.sync_state().await?;
+
+    std::fs::create_dir_all(&config.store_dir).map_err(|err| {
+        CliError::InitializationError(format!("failed to create worker store dir: {err}"))
+    })?;
+
+    let per_worker_tps = (config.target_tps / worker_accounts.len() as f64).max(0.01);
+    let interval_duration = Duration::from_secs_f64(1.0 / per_worker_tps);
+    let deadline = Instant::now() + config.duration;
+
+    let mut handles = Vec::with_capacity(worker_accounts.len());
+    for (index, worker_account) in worker_accounts.into_iter().enumerate() {
+        handles.push(tokio::spawn(run_worker(
+            Arc::clone(&config.rpc_client),
+            Arc::clone(keystore),
+            config.store_dir.join(format!("emitter-worker-{index}.sqlite3")),
+            worker_account,
+            faucet_account.clone(),
+            interval_duration,
+            deadline,
+        )));
+    }
+
+    let mut stats = Vec::with_capacity(handles.len());
+    for handle in handles {
+        match handle.await {
+            Ok(Ok(worker_stats)) => stats.push(worker_stats),
+            Ok(Err(err)) => eprintln!("emitter worker failed: {err}"),
+            Err(err) => eprintln!("emitter worker task panicked: {err}"),
+        }
+    }
+
+    let submitted: u64 = stats.iter().map(|s| s.submitted).sum();
+    let succeeded: u64 = stats.iter().map(|s| s.succeeded).sum();
+    let confirmed: u64 = stats.iter().map(|s| s.confirmed).sum();
+
+    let mut submit_latencies: Vec<Duration> = stats
+        .iter()
+        .flat_map(|s| s.submit_latencies.iter().copied())
+        .collect();
+    submit_latencies.sort();
+
+    let mut confirm_latencies: Vec<Duration> = stats
+        .iter()
+        .flat_map(|s| s.confirm_latencies.iter().copied())
+        .collect();
+    confirm_latencies.sort();
+
+    Ok(EmitterReport {
+        submitted,
+        succeeded,
+        failed: submitted - succeeded,
+        confirmed,
+        effective_tps: submitted as f64 / config.duration.as_secs_f64(),
+        submit_p50_latency: percentile(&submit_latencies, 0.50),
+        submit_p95_latency: percentile(&submit_latencies, 0.95),
+        submit_p99_latency: percentile(&submit_latencies, 0.99),
+        confirm_p50_latency: percentile(&confirm_latencies, 0.50),
+        confirm_p95_latency: percentile(&confirm_latencies, 0.95),
+        confirm_p99_latency: percentile(&confirm_latencies, 0.99),
+    })
+}
+
+async fn create_worker_account(
+    client: &mut Client<GrpcClient>,
+    keystore: &Arc<FilesystemKeyStore>,
+) -> Result<Account, CliError> {
+    use miden_client::auth::AuthSecretKey;
+
+    let mut init_seed = [0_u8; 32];
+    client.rng().fill_bytes(&mut init_seed);
+    let key_pair = AuthSecretKey::new_falcon512_rpo();
+
+    let account = crate::account_ops::build_wallet_account(init_seed, &key_pair)?;
+
+    client.add_account(&account, false).await?;
+    keystore
+        .add_key(&key_pair)
+        .map_err(|err| CliError::InitializationError(err.to_string()))?;
+
+    Ok(account)
+}
+
+/// Drives one worker's submission loop on its own `Client`, then waits for
+/// its own submissions to confirm.
+async fn run_worker(
+    rpc_client: Arc<GrpcClient>,
+    keystore: Arc<FilesystemKeyStore>,
+    store_path: PathBuf,
+    worker_account: Account,
+    faucet_account: Account,
+    interval_duration: Duration,
+    deadline: Instant,
+) -> Result<WorkerStats, CliError> {
+    let mut worker_client = ClientBuilder::new()
+        .rpc(rpc_client)
+        .sqlite_store(store_path)
+        .authenticator(keystore)
+        .in_debug_mode(true.into())
+        .build()
+        .await?;
+
+    // Import the worker's own account and the faucet account so this
+    // dedicated store can track their state locally.
+    worker_client.add_account(&worker_account, false).await?;
+    worker_client.add_account(&faucet_account, false).await?;
+    worker_client.sync_state().await?;
+
+    let worker_id = worker_account.id();
+    let faucet_id = faucet_account.id();
+
+    let mut submitted = 0_u64;
+    let mut succeeded = 0_u64;
+    let mut submit_latencies = Vec::new();
+    let mut pending_notes: HashMap<NoteId, Instant> = HashMap::new();
+
+    let mut ticker = tokio::time::interval(interval_duration);
+    while Instant::now() < deadline {
+        ticker.tick().await;
+        submitted += 1;
+        let started = Instant::now();
+
+        match submit_one(&mut worker_client, faucet_id, worker_id).await {
+            Ok(note_id) => {
+                succeeded += 1;
+                submit_latencies.push(started.elapsed());
+                if let Some(note_id) = note_id {
+                    pending_notes.insert(note_id, started);
+                }
+            }
+            Err(err) => eprintln!("worker {worker_id:?}: {err}"),
+        }
+    }
+
+    let confirm_latencies =
+        match wait_for_confirmations(&mut worker_client, worker_id, &mut pending_notes).await {
+            Ok(confirm_latencies) => confirm_latencies,
+            Err(err @ CliError::ConfirmationTimeout(_)) => {
+                // Notes that genuinely never confirmed within the window;
+                // report it rather than silently dropping it, but still
+                // return whatever else this worker gathered.
+                eprintln!("worker {worker_id:?}: {err}");
+                Vec::new()
+            }
+            Err(err) => return Err(err),
+        };
+    let confirmed = confirm_latencies.len() as u64;
+
+    Ok(WorkerStats {
+        submitted,
+        succeeded,
+        confirmed,
+        submit_latencies,
+        confirm_latencies,
+    })
+}
+
+/// Submits a single 1-token mint-to-self transaction, returning the minted
+/// note's ID so the caller can track when it confirms.
+async fn submit_one(
+    client: &mut Client<GrpcClient>,
+    faucet_id: AccountId,
+    worker_id: AccountId,
+) -> Result<Option<NoteId>, CliError> {
+    let transaction_request = crate::account_ops::build_mint_request(faucet_id, worker_id, 1, client.rng())
+        .map_err(|err| CliError::SubmissionError(err.to_string()))?;
+
+    let note_id = transaction_request
+        .expected_output_notes()
+        .next()
+        .map(|note| note.id());
+
+    client
+        .submit_new_transaction(faucet_id, transaction_request)
+        .await
+        .map_err(|err| CliError::SubmissionError(err.to_string()))?;
+
+    Ok(note_id)
+}
+
+/// Polls `sync_state` until every note in `pending` has become consumable,
+/// or gives up after [`MAX_CONFIRMATION_POLLS`] attempts, returning the
+/// confirmation latency (submit-to-consumable) for each note that confirmed.
+///
+/// Returns [`CliError::ConfirmationTimeout`] if any notes are still pending
+/// once polling gives up, rather than silently reporting a partial count.
+async fn wait_for_confirmations(
+    client: &mut Client<GrpcClient>,
+    worker_id: AccountId,
+    pending: &mut HashMap<NoteId, Instant>,
+) -> Result<Vec<Duration>, CliError> {
+    let mut confirm_latencies = Vec::new();
+
+    for _ in 0..MAX_CONFIRMATION_POLLS {
+        if pending.is_empty() {
+            break;
+        }
+
+        client.sync_state().await.map_err(CliError::from)?;
+        let consumable_notes = client
+            .get_consumable_notes(Some(worker_id))
+            .await
+            .map_err(CliError::from)?;
+
+        for (note, _) in consumable_notes {
+            let note: Note = note
+                .try_into()
+                .map_err(|err| CliError::NoteCreationError(format!("{err:?}")))?;
+            if let Some(submitted_at) = pending.remove(&note.id()) {
+                confirm_latencies.push(submitted_at.elapsed());
+            }
+        }
+
+        if pending.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    if !pending.is_empty() {
+        return Err(CliError::ConfirmationTimeout(pending.len() as u64));
+    }
+
+    Ok(confirm_latencies)
+}
+
+fn percentile(sorted_latencies: &[Duration], p: f64) -> Duration {
+    if sorted_latencies.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = ((sorted_latencies.len() as f64 - 1.0) * p).round() as usize;
+    sorted_latencies[rank.min(sorted_latencies.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_of_empty_slice_is_zero() {
+        assert_eq!(percentile(&[], 0.50), Duration::ZERO);
+    }
+
+    #[test]
+    fn percentile_of_single_element_is_that_element() {
+        let latencies = [Duration::from_millis(42)];
+        assert_eq!(percentile(&latencies, 0.50), Duration::from_millis(42));
+        assert_eq!(percentile(&latencies, 0.99), Duration::from_millis(42));
+    }
+
+    #[test]
+    fn percentile_picks_the_expected_rank() {
+        let latencies: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&latencies, 0.50), Duration::from_millis(51));
+        assert_eq!(percentile(&latencies, 0.95), Duration::from_millis(95));
+        assert_eq!(percentile(&latencies, 0.99), Duration::from_millis(99));
+    }
+}
+
+impl std::fmt::Display for EmitterReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "submitted={} succeeded={} failed={} confirmed={} effective_tps={:.2} \
+             submit_latency(p50={:?} p95={:?} p99={:?}) \
+             confirm_latency(p50={:?} p95={:?} p99={:?})",
+            self.submitted,
+            self.succeeded,
+            self.failed,
+            self.confirmed,
+            self.effective_tps,
+            self.submit_p50_latency,
+            self.submit_p95_latency,
+            self.submit_p99_latency,
+            self.confirm_p50_latency,
+            self.confirm_p95_latency,
+            self.confirm_p99_latency,
+        )
+    }
+}
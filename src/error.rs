@@ -0,0 +1,49 @@
+use miden_client::ClientError;
+
+/// Error types for Miden client operations
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    /// Failed to initialize the Miden client
+    #[error("failed to initialize miden client: {0}")]
+    InitializationError(String),
+
+    /// Failed to create a note
+    #[error("failed to create note: {0}")]
+    NoteCreationError(String),
+
+    /// Failed to submit transaction
+    #[error("failed to submit transaction: {0}")]
+    TransactionError(String),
+
+    /// Failed to sync state
+    #[error("failed to sync state: {0}")]
+    SyncError(String),
+
+    /// Account not found
+    #[error("account not found: {0}")]
+    AccountNotFound(String),
+
+    /// Failed to derive a key from a mnemonic or passphrase
+    #[error("key derivation failed: {0}")]
+    KeyDerivationError(String),
+
+    /// Failed to encrypt, decrypt, or parse an account backup
+    #[error("backup error: {0}")]
+    BackupError(String),
+
+    /// Propagated from the underlying `miden_client`
+    #[error(transparent)]
+    Client(#[from] ClientError),
+
+    /// A load-generation worker failed to submit a transaction
+    #[error("transaction submission failed: {0}")]
+    SubmissionError(String),
+
+    /// A submitted transaction did not confirm within the expected window
+    #[error("timed out waiting for {0} transaction(s) to confirm")]
+    ConfirmationTimeout(u64),
+
+    /// A recipient exceeded their rolling-window mint allowance
+    #[error("rate limited: retry in {0} second(s)")]
+    RateLimited(u64),
+}
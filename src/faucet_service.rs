@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use miden_client::Client;
+use miden_client::account::AccountId;
+use miden_client::note::{NoteAttachment, NoteType, create_p2id_note};
+use miden_client::rpc::GrpcClient;
+use miden_client::transaction::{OutputNote, TransactionRequestBuilder};
+use miden_protocol::asset::FungibleAsset;
+
+use crate::CliError;
+
+/// Per-recipient minting window: when it started, and how much has been
+/// minted to this recipient since then.
+struct RecipientWindow {
+    window_start: Instant,
+    total_minted: u64,
+}
+
+/// Enforces a per-request cap and a rolling-window aggregate cap per recipient.
+pub struct FaucetLimiter {
+    per_request_cap: u64,
+    window_cap: u64,
+    window_period: Duration,
+    recipients: HashMap<AccountId, RecipientWindow>,
+}
+
+/// Which cap bound a [`MintDecision::Capped`] result.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CapKind {
+    /// The fixed per-request amount cap.
+    PerRequest,
+    /// The recipient's rolling-window aggregate cap.
+    Window,
+}
+
+/// Outcome of checking a mint request against the limiter.
+pub enum MintDecision {
+    /// Mint the full requested amount.
+    Full(u64),
+    /// Mint only `capped_amount`, attaching a memo explaining why.
+    Capped {
+        capped_amount: u64,
+        requested: u64,
+        cap: CapKind,
+    },
+    /// Reject the request outright; retry after `retry_after` elapses.
+    RateLimited { retry_after: Duration },
+}
+
+impl FaucetLimiter {
+    pub fn new(per_request_cap: u64, window_cap: u64, window_period: Duration) -> Self {
+        Self {
+            per_request_cap,
+            window_cap,
+            window_period,
+            recipients: HashMap::new(),
+        }
+    }
+
+    /// Checks `requested_amount` for `recipient` against both caps, sliding
+    /// the recipient's window forward if it has expired.
+    pub fn check(&mut self, recipient: AccountId, requested_amount: u64) -> MintDecision {
+        let now = Instant::now();
+        let window = self
+            .recipients
+            .entry(recipient)
+            .or_insert_with(|| RecipientWindow {
+                window_start: now,
+                total_minted: 0,
+            });
+
+        if now.duration_since(window.window_start) > self.window_period {
+            window.window_start = now;
+            window.total_minted = 0;
+        }
+
+        if window.total_minted >= self.window_cap {
+            let retry_after = self.window_period - now.duration_since(window.window_start);
+            return MintDecision::RateLimited { retry_after };
+        }
+
+        let remaining_in_window = self.window_cap - window.total_minted;
+        let per_request_capped = requested_amount.min(self.per_request_cap);
+        let window_capped = requested_amount.min(remaining_in_window);
+        let final_amount = per_request_capped.min(window_capped);
+
+        window.total_minted += final_amount;
+
+        if final_amount == requested_amount {
+            MintDecision::Full(final_amount)
+        } else {
+            let cap = if window_capped < per_request_capped {
+                CapKind::Window
+            } else {
+                CapKind::PerRequest
+            };
+            MintDecision::Capped {
+                capped_amount: final_amount,
+                requested: requested_amount,
+                cap,
+            }
+        }
+    }
+}
+
+/// A single incoming faucet request.
+pub struct MintRequest {
+    pub recipient: AccountId,
+    pub amount: u64,
+}
+
+/// Services one [`MintRequest`] against `limiter`, submitting a mint
+/// transaction from `faucet_id` that respects the per-request and
+/// rolling-window caps.
+pub async fn handle_mint_request(
+    client: &mut Client<GrpcClient>,
+    faucet_id: AccountId,
+    limiter: &mut FaucetLimiter,
+    request: MintRequest,
+) -> Result<(), CliError> {
+    match limiter.check(request.recipient, request.amount) {
+        MintDecision::Full(amount) => {
+            let fungible_asset = FungibleAsset::new(faucet_id, amount)
+                .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+            let transaction_request = TransactionRequestBuilder::new()
+                .build_mint_fungible_asset(
+                    fungible_asset,
+                    request.recipient,
+                    NoteType::Public,
+                    client.rng(),
+                )
+                .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+            client
+                .submit_new_transaction(faucet_id, transaction_request)
+                .await?;
+            println!("Minted {} tokens for {:?}", amount, request.recipient);
+            Ok(())
+        }
+        MintDecision::Capped {
+            capped_amount,
+            requested,
+            cap,
+        } => {
+            let fungible_asset = FungibleAsset::new(faucet_id, capped_amount)
+                .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+            let reason = match cap {
+                CapKind::PerRequest => "the per-request cap",
+                CapKind::Window => "your rolling-window allowance",
+            };
+            let memo = NoteAttachment::with_memo(format!(
+                "requested {requested} tokens but only {capped_amount} were minted: \
+                 exceeded {reason}"
+            ))
+            .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+            let p2id_note = create_p2id_note(
+                faucet_id,
+                request.recipient,
+                vec![fungible_asset.into()],
+                NoteType::Public,
+                memo,
+                client.rng(),
+            )?;
+            let transaction_request = TransactionRequestBuilder::new()
+                .own_output_notes(vec![OutputNote::Full(p2id_note)])
+                .build()
+                .map_err(|err| CliError::NoteCreationError(err.to_string()))?;
+            client
+                .submit_new_transaction(faucet_id, transaction_request)
+                .await?;
+            println!(
+                "Minted capped amount of {} tokens (requested {}) for {:?}",
+                capped_amount, requested, request.recipient
+            );
+            Ok(())
+        }
+        MintDecision::RateLimited { retry_after } => {
+            Err(CliError::RateLimited(retry_after.as_secs()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use miden_protocol::account::{AccountIdVersion, AccountStorageMode, AccountType};
+
+    fn dummy_recipient(tag: u8) -> AccountId {
+        let mut init_seed = [0_u8; 15];
+        init_seed[0] = tag;
+        AccountId::dummy(
+            init_seed,
+            AccountIdVersion::Version0,
+            AccountType::RegularAccountUpdatableCode,
+            AccountStorageMode::Public,
+        )
+    }
+
+    #[test]
+    fn full_amount_is_minted_when_under_both_caps() {
+        let mut limiter = FaucetLimiter::new(1_000, 500, Duration::from_secs(60));
+        match limiter.check(dummy_recipient(1), 100) {
+            MintDecision::Full(amount) => assert_eq!(amount, 100),
+            _ => panic!("expected a full mint"),
+        }
+    }
+
+    #[test]
+    fn per_request_cap_bounds_a_single_large_request() {
+        let mut limiter = FaucetLimiter::new(100, 10_000, Duration::from_secs(60));
+        match limiter.check(dummy_recipient(1), 500) {
+            MintDecision::Capped {
+                capped_amount,
+                requested,
+                cap,
+            } => {
+                assert_eq!(capped_amount, 100);
+                assert_eq!(requested, 500);
+                assert_eq!(cap, CapKind::PerRequest);
+            }
+            _ => panic!("expected a capped mint"),
+        }
+    }
+
+    #[test]
+    fn window_cap_bounds_a_fresh_recipients_first_request() {
+        // per_request_cap is looser than window_cap, so the window cap must
+        // be the one that binds here, and the memo must say so.
+        let mut limiter = FaucetLimiter::new(1_000, 500, Duration::from_secs(60));
+        match limiter.check(dummy_recipient(1), 600) {
+            MintDecision::Capped {
+                capped_amount,
+                requested,
+                cap,
+            } => {
+                assert_eq!(capped_amount, 500);
+                assert_eq!(requested, 600);
+                assert_eq!(cap, CapKind::Window);
+            }
+            _ => panic!("expected a capped mint"),
+        }
+    }
+
+    #[test]
+    fn window_cap_accumulates_across_requests_and_then_rate_limits() {
+        let mut limiter = FaucetLimiter::new(1_000, 500, Duration::from_secs(60));
+        let recipient = dummy_recipient(1);
+
+        match limiter.check(recipient, 400) {
+            MintDecision::Full(amount) => assert_eq!(amount, 400),
+            _ => panic!("expected a full mint"),
+        }
+
+        match limiter.check(recipient, 200) {
+            MintDecision::Capped {
+                capped_amount,
+                requested,
+                cap,
+            } => {
+                assert_eq!(capped_amount, 100);
+                assert_eq!(requested, 200);
+                assert_eq!(cap, CapKind::Window);
+            }
+            _ => panic!("expected the second request to be capped to the window's remainder"),
+        }
+
+        match limiter.check(recipient, 200) {
+            MintDecision::RateLimited { .. } => {}
+            _ => panic!("expected the third request to be rate limited"),
+        }
+    }
+
+    #[test]
+    fn separate_recipients_have_independent_windows() {
+        let mut limiter = FaucetLimiter::new(1_000, 500, Duration::from_secs(60));
+
+        match limiter.check(dummy_recipient(1), 500) {
+            MintDecision::Full(amount) => assert_eq!(amount, 500),
+            _ => panic!("expected a full mint"),
+        }
+        match limiter.check(dummy_recipient(2), 500) {
+            MintDecision::Full(amount) => assert_eq!(amount, 500),
+            _ => panic!("expected recipient 2's window to be independent"),
+        }
+    }
+}
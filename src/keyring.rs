@@ -0,0 +1,30 @@
+//! Abstracts over the keystore backend so account-creation logic can add a
+//! freshly derived key without caring whether it is running against the
+//! native filesystem keystore or the in-browser wasm keystore.
+
+use miden_client::auth::AuthSecretKey;
+use miden_client::keystore::FilesystemKeyStore;
+#[cfg(target_arch = "wasm32")]
+use miden_client::keystore::WebKeyStore;
+
+use crate::CliError;
+
+/// A keystore that can accept a newly derived auth key.
+pub trait KeyRing {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), CliError>;
+}
+
+impl KeyRing for FilesystemKeyStore {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), CliError> {
+        FilesystemKeyStore::add_key(self, key)
+            .map_err(|err| CliError::InitializationError(err.to_string()))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl KeyRing for WebKeyStore {
+    fn add_key(&self, key: &AuthSecretKey) -> Result<(), CliError> {
+        WebKeyStore::add_key(self, key)
+            .map_err(|err| CliError::InitializationError(err.to_string()))
+    }
+}
@@ -0,0 +1,22 @@
+//! Shared library crate backing both the native demo binary (`src/main.rs`)
+//! and the wasm bindings (`src/wasm.rs`). Keeping the account/transaction
+//! logic here lets both front ends build their accounts and transaction
+//! requests through the exact same code instead of maintaining separate
+//! copies; each front end submits the built requests on its own `Client`.
+//!
+//! Producing an actual `wasm-bindgen` package from `wasm` still requires a
+//! `Cargo.toml` with `[lib] crate-type = ["cdylib", "rlib"]` — this tree
+//! ships no manifest at all, so that step is out of scope here; `wasm` is
+//! written as it would need to look once one exists.
+
+pub mod account_ops;
+pub mod emitter;
+pub mod error;
+pub mod faucet_service;
+pub mod keyring;
+pub mod mnemonic;
+pub mod notes_stream;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+
+pub use error::CliError;
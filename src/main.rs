@@ -1,16 +1,10 @@
-use miden_standards::account::auth::AuthFalcon512Rpo;
 use rand::RngCore;
 use std::sync::Arc;
 use tokio::time::Duration;
 
 use miden_client::{
-    ClientError,
-    account::{
-        AccountId,
-        component::{BasicFungibleFaucet, BasicWallet},
-    },
+    account::AccountId,
     address::NetworkId,
-    auth::AuthSecretKey,
     builder::ClientBuilder,
     keystore::FilesystemKeyStore,
     note::{Note, NoteAttachment, NoteType, create_p2id_note},
@@ -19,36 +13,19 @@ use miden_client::{
 };
 use miden_client_sqlite_store::ClientBuilderSqliteExt;
 use miden_protocol::{
-    Felt,
-    account::{AccountBuilder, AccountIdVersion, AccountStorageMode, AccountType},
-    asset::{FungibleAsset, TokenSymbol},
+    account::{AccountIdVersion, AccountStorageMode, AccountType},
+    asset::FungibleAsset,
 };
-/// Error types for Miden client operations
-#[derive(Debug, thiserror::Error)]
-pub enum CliError {
-    /// Failed to initialize the Miden client
-    #[error("failed to initialize miden client: {0}")]
-    InitializationError(String),
-
-    /// Failed to create a note
-    #[error("failed to create note: {0}")]
-    NoteCreationError(String),
-
-    /// Failed to submit transaction
-    #[error("failed to submit transaction: {0}")]
-    TransactionError(String),
-
-    /// Failed to sync state
-    #[error("failed to sync state: {0}")]
-    SyncError(String),
-
-    /// Account not found
-    #[error("account not found: {0}")]
-    AccountNotFound(String),
-}
+
+use futures::StreamExt;
+use miden_rust_demo::emitter::{self, EmitterConfig};
+use miden_rust_demo::faucet_service::{self, FaucetLimiter, MintRequest};
+use miden_rust_demo::mnemonic::{self, AccountBackup, AccountKind};
+use miden_rust_demo::notes_stream;
+use miden_rust_demo::{account_ops, CliError};
 
 #[tokio::main]
-async fn main() -> Result<(), ClientError> {
+async fn main() -> Result<(), CliError> {
     // Initialize client
     //
     let endpoint = Endpoint::try_from("http://localhost:57291").unwrap();
@@ -65,7 +42,7 @@ async fn main() -> Result<(), ClientError> {
     let store_path = std::path::PathBuf::from("./v13/store.sqlite3");
 
     let mut client = ClientBuilder::new()
-        .rpc(rpc_client)
+        .rpc(rpc_client.clone())
         .sqlite_store(store_path)
         .authenticator(keystore.clone())
         .in_debug_mode(true.into())
@@ -80,20 +57,16 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     println!("\n[STEP 1] Creating a new account for Alice");
 
-    // Account seed
-    let mut init_seed = [0_u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-
-    let key_pair = AuthSecretKey::new_falcon512_rpo();
+    // Derive Alice's account seed and auth key pair from a fresh BIP39 mnemonic,
+    // so the account can be fully recreated later from the phrase alone.
+    let alice_mnemonic = mnemonic::generate_mnemonic(128)?;
+    println!("Alice's recovery phrase: {}", alice_mnemonic);
+    let alice_seed = mnemonic::seed_from_mnemonic(&alice_mnemonic, "");
+    let init_seed = mnemonic::derive_account_seed(&alice_seed);
+    let key_pair = mnemonic::derive_falcon_key_pair(&alice_seed);
 
     // Build the account
-    let alice_account = AccountBuilder::new(init_seed)
-        .account_type(AccountType::RegularAccountUpdatableCode)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-        .with_component(BasicWallet)
-        .build()
-        .unwrap();
+    let alice_account = account_ops::build_wallet_account(init_seed, &key_pair)?;
 
     // Add the account to the client
     client.add_account(&alice_account, false).await?;
@@ -104,31 +77,34 @@ async fn main() -> Result<(), ClientError> {
     let alice_account_id_bech32 = alice_account.id().to_bech32(NetworkId::Testnet);
     println!("Alice's account ID: {:?}", alice_account_id_bech32);
 
+    // Write an encrypted backup next to the keystore so Alice's account survives
+    // a lost or corrupted keystore directory.
+    let alice_backup = AccountBackup {
+        account_id: alice_account.id(),
+        key_pair: key_pair.clone(),
+        mnemonic: alice_mnemonic,
+        kind: AccountKind::Wallet,
+    };
+    let alice_passphrase = mnemonic::read_passphrase("ALICE")?;
+    let alice_backup_bytes = mnemonic::encrypt_backup(&alice_backup, &alice_passphrase)?;
+    std::fs::write("./v13/alice.backup", &alice_backup_bytes)
+        .map_err(|err| CliError::BackupError(format!("failed to write backup: {err}")))?;
+
     //------------------------------------------------------------
     // STEP 2: Deploy a fungible faucet
     //------------------------------------------------------------
     println!("\n[STEP 2] Deploying a new fungible faucet.");
 
-    // Faucet seed
-    let mut init_seed = [0u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-
-    // Faucet parameters
-    let symbol = TokenSymbol::new("MID").unwrap();
-    let decimals = 8;
-    let max_supply = Felt::new(1_000_000);
-
-    // Generate key pair
-    let key_pair = AuthSecretKey::new_falcon512_rpo();
+    // Derive the faucet's account seed and auth key pair from a fresh mnemonic,
+    // same as Alice's wallet above.
+    let faucet_mnemonic = mnemonic::generate_mnemonic(128)?;
+    println!("Faucet recovery phrase: {}", faucet_mnemonic);
+    let faucet_seed = mnemonic::seed_from_mnemonic(&faucet_mnemonic, "");
+    let init_seed = mnemonic::derive_account_seed(&faucet_seed);
+    let key_pair = mnemonic::derive_falcon_key_pair(&faucet_seed);
 
     // Build the faucet account
-    let faucet_account = AccountBuilder::new(init_seed)
-        .account_type(AccountType::FungibleFaucet)
-        .storage_mode(AccountStorageMode::Public)
-        .with_auth_component(AuthFalcon512Rpo::new(key_pair.public_key().to_commitment()))
-        .with_component(BasicFungibleFaucet::new(symbol, decimals, max_supply).unwrap())
-        .build()
-        .unwrap();
+    let faucet_account = account_ops::build_faucet_account(init_seed, &key_pair, "MID", 8, 1_000_000)?;
 
     // Add the faucet to the client
     client.add_account(&faucet_account, false).await?;
@@ -139,6 +115,49 @@ async fn main() -> Result<(), ClientError> {
     let faucet_account_id_bech32 = faucet_account.id().to_bech32(NetworkId::Testnet);
     println!("Faucet account ID: {:?}", faucet_account_id_bech32);
 
+    // Back up the faucet's account the same way as Alice's wallet.
+    let faucet_backup = AccountBackup {
+        account_id: faucet_account.id(),
+        key_pair: key_pair.clone(),
+        mnemonic: faucet_mnemonic,
+        kind: AccountKind::FungibleFaucet {
+            symbol: "MID".to_string(),
+            decimals: 8,
+            max_supply: 1_000_000,
+        },
+    };
+    let faucet_passphrase = mnemonic::read_passphrase("FAUCET")?;
+    let faucet_backup_bytes = mnemonic::encrypt_backup(&faucet_backup, &faucet_passphrase)?;
+    std::fs::write("./v13/faucet.backup", faucet_backup_bytes)
+        .map_err(|err| CliError::BackupError(format!("failed to write backup: {err}")))?;
+
+    // Prove the backup actually recovers the account: restore Alice's wallet
+    // into a fresh client/keystore/store, as if the original keystore had
+    // been lost.
+    println!("\nVerifying Alice's backup restores into a fresh client...");
+    let restore_keystore_path = std::path::PathBuf::from("./v13/restore-demo/keystore");
+    let restore_keystore = Arc::new(FilesystemKeyStore::new(restore_keystore_path).unwrap());
+    let restore_store_path = std::path::PathBuf::from("./v13/restore-demo/store.sqlite3");
+    let mut restore_client = ClientBuilder::new()
+        .rpc(Arc::new(GrpcClient::new(&endpoint, timeout_ms)))
+        .sqlite_store(restore_store_path)
+        .authenticator(restore_keystore.clone())
+        .in_debug_mode(true.into())
+        .build()
+        .await?;
+    restore_client.sync_state().await?;
+    let restored_account_id = mnemonic::restore_account(
+        &mut restore_client,
+        &restore_keystore,
+        &alice_backup_bytes,
+        &alice_passphrase,
+    )
+    .await?;
+    println!(
+        "Restored Alice's account from backup: {:?}",
+        restored_account_id.to_bech32(NetworkId::Testnet)
+    );
+
     // Resync to show newly deployed faucet
     client.sync_state().await?;
     tokio::time::sleep(Duration::from_secs(2)).await;
@@ -148,18 +167,73 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     println!("\n[STEP 3] Minting 5 notes of 100 tokens each for Alice.");
 
+    // Set MIDEN_DEMO_EMITTER_TPS to benchmark the node instead of running the
+    // fixed 5-note mint loop below.
+    if let Ok(target_tps) = std::env::var("MIDEN_DEMO_EMITTER_TPS") {
+        let target_tps: f64 = target_tps
+            .parse()
+            .map_err(|err| CliError::InitializationError(format!("invalid target tps: {err}")))?;
+        let config = EmitterConfig {
+            num_workers: 4,
+            target_tps,
+            duration: Duration::from_secs(30),
+            funding_amount: 1_000,
+            rpc_client: rpc_client.clone(),
+            store_dir: std::path::PathBuf::from("./v13/emitter-workers"),
+        };
+        let report =
+            emitter::run_emitter(&mut client, &keystore, &faucet_account, config).await?;
+        println!("Emitter run complete: {}", report);
+        return Ok(());
+    }
+
+    // Set MIDEN_DEMO_FAUCET_SERVICE to the number of seconds to run the
+    // faucet as a long-lived, rate limited service instead of the fixed
+    // 5-note mint loop below. Requests keep arriving on an interval for the
+    // whole window, the way an actual faucet service would, rather than
+    // stopping after a handful of synthetic calls.
+    if let Ok(run_seconds) = std::env::var("MIDEN_DEMO_FAUCET_SERVICE") {
+        let run_seconds: u64 = run_seconds
+            .parse()
+            .map_err(|err| CliError::InitializationError(format!("invalid run seconds: {err}")))?;
+        let mut limiter = FaucetLimiter::new(200, 500, Duration::from_secs(60));
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(run_seconds);
+        let mut ticker = tokio::time::interval(Duration::from_secs(5));
+        let mut requests_handled: u64 = 0;
+
+        while tokio::time::Instant::now() < deadline {
+            ticker.tick().await;
+            requests_handled += 1;
+            let request = MintRequest {
+                recipient: alice_account.id(),
+                amount: 100 * (1 + requests_handled % 5),
+            };
+            match faucet_service::handle_mint_request(
+                &mut client,
+                faucet_account.id(),
+                &mut limiter,
+                request,
+            )
+            .await
+            {
+                Ok(()) => {}
+                Err(CliError::RateLimited(retry_after)) => {
+                    println!("Rate limited; retry in {retry_after}s");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        println!("Faucet service ran for {run_seconds}s, handling {requests_handled} request(s).");
+        client.sync_state().await?;
+        return Ok(());
+    }
+
     let amount: u64 = 100;
-    let fungible_asset = FungibleAsset::new(faucet_account.id(), amount).unwrap();
 
     for i in 1..=5 {
-        let transaction_request = TransactionRequestBuilder::new()
-            .build_mint_fungible_asset(
-                fungible_asset,
-                alice_account.id(),
-                NoteType::Public,
-                client.rng(),
-            )
-            .unwrap();
+        let transaction_request =
+            account_ops::build_mint_request(faucet_account.id(), alice_account.id(), amount, client.rng())?;
 
         println!("tx request built");
 
@@ -181,38 +255,32 @@ async fn main() -> Result<(), ClientError> {
     //------------------------------------------------------------
     println!("\n[STEP 4] Alice will now consume all of her notes to consolidate them.");
 
-    // Consume all minted notes in a single transaction
-    loop {
-        // Resync to get the latest data
-        client.sync_state().await?;
-
-        let consumable_notes = client
-            .get_consumable_notes(Some(alice_account.id()))
-            .await?;
-        let list_of_notes: Vec<Note> = consumable_notes.iter().map(|(note, _)| note.try_into().unwrap()).collect();
-
-        if list_of_notes.len() == 5 {
-            println!("Found 5 consumable notes for Alice. Consuming them now...");
-            let transaction_request = TransactionRequestBuilder::new()
-                .build_consume_notes(list_of_notes)
-                .unwrap();
-
-            let tx_id = client
-                .submit_new_transaction(alice_account.id(), transaction_request)
-                .await?;
-            println!(
-                "All of Alice's notes consumed successfully. TX: {:?}",
-                tx_id
-            );
-            break;
-        } else {
-            println!(
-                "Currently, Alice has {} consumable notes. Waiting...",
-                list_of_notes.len()
-            );
-            tokio::time::sleep(Duration::from_secs(3)).await;
+    // Collect notes as they become consumable instead of busy-polling for an
+    // exact count of 5.
+    let list_of_notes: Vec<Note> = {
+        let stream = notes_stream::consumable_notes_stream(&mut client, alice_account.id());
+        futures::pin_mut!(stream);
+        let mut notes = Vec::with_capacity(5);
+        while notes.len() < 5 {
+            match stream.next().await {
+                Some(Ok(note)) => notes.push(note),
+                Some(Err(err)) => return Err(err),
+                None => break,
+            }
         }
-    }
+        notes
+    };
+
+    println!("Found {} consumable notes for Alice. Consuming them now...", list_of_notes.len());
+    let transaction_request = account_ops::build_consume_notes_request(list_of_notes)?;
+
+    let tx_id = client
+        .submit_new_transaction(alice_account.id(), transaction_request)
+        .await?;
+    println!(
+        "All of Alice's notes consumed successfully. TX: {:?}",
+        tx_id
+    );
 
     //------------------------------------------------------------
     // STEP 5: Alice sends 5 notes of 50 tokens to 5 users
@@ -278,22 +346,14 @@ async fn main() -> Result<(), ClientError> {
     );
 
     let send_amount = 50;
-    let fungible_asset = FungibleAsset::new(faucet_account.id(), send_amount).unwrap();
-
-    let p2id_note = create_p2id_note(
+    let transaction_request = account_ops::build_send_p2id_request(
         alice_account.id(),
         target_account_id,
-        vec![fungible_asset.into()],
-        NoteType::Public,
-        NoteAttachment::default(),
+        faucet_account.id(),
+        send_amount,
         client.rng(),
     )?;
 
-    let transaction_request = TransactionRequestBuilder::new()
-        .own_output_notes(vec![OutputNote::Full(p2id_note)])
-        .build()
-        .unwrap();
-
     let tx_id = client
         .submit_new_transaction(alice_account.id(), transaction_request)
         .await?;
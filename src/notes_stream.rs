@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use async_stream::try_stream;
+use futures::Stream;
+use miden_client::Client;
+use miden_client::account::AccountId;
+use miden_client::note::{Note, NoteId};
+use miden_client::rpc::GrpcClient;
+
+use crate::CliError;
+
+/// Minimum time between consecutive `sync_state` calls, so a fast consumer
+/// doesn't spam the RPC endpoint while waiting for new notes.
+const SYNC_DEBOUNCE: Duration = Duration::from_secs(3);
+
+/// Returns a stream that yields each note as it becomes consumable for
+/// `account_id`.
+///
+/// Internally drives `sync_state` on a debounced interval; RPC failures are
+/// surfaced as `Err` items instead of panicking. Callers decide when enough
+/// notes have arrived, e.g. via `.take(n)` or by folding until a balance
+/// threshold is reached, replacing the old `== 5` equality check.
+pub fn consumable_notes_stream(
+    client: &mut Client<GrpcClient>,
+    account_id: AccountId,
+) -> impl Stream<Item = Result<Note, CliError>> + '_ {
+    try_stream! {
+        let mut seen = Vec::<NoteId>::new();
+
+        loop {
+            client.sync_state().await.map_err(CliError::from)?;
+
+            let consumable_notes = client
+                .get_consumable_notes(Some(account_id))
+                .await
+                .map_err(CliError::from)?;
+
+            for (note, _) in consumable_notes {
+                let note: Note = note
+                    .try_into()
+                    .map_err(|err| CliError::NoteCreationError(format!("{err:?}")))?;
+                if !seen.contains(&note.id()) {
+                    seen.push(note.id());
+                    yield note;
+                }
+            }
+
+            tokio::time::sleep(SYNC_DEBOUNCE).await;
+        }
+    }
+}
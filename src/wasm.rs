@@ -0,0 +1,195 @@
+//! Browser-facing bindings for the wallet/faucet flow demonstrated in `main`.
+//!
+//! Built only for `wasm32` targets (`wasm-pack build --target web`). Swaps
+//! the filesystem keystore and sqlite store used by the native demo for an
+//! in-memory store and a gRPC-web compatible RPC client, then drives the
+//! same [`crate::account_ops`] builders the native demo uses, so the two
+//! front ends share one implementation of "what a wallet/faucet/mint/send
+//! looks like" and only differ in how the `Client` itself is wired up.
+//!
+//! The client and keystore live on a [`WasmSession`] that the caller holds
+//! onto across calls, so an account created by one call is still known to
+//! the client a later call runs against, instead of vanishing with a fresh
+//! in-memory store every time.
+
+use miden_client::account::AccountId;
+use miden_client::address::NetworkId;
+use miden_client::auth::AuthSecretKey;
+use miden_client::builder::ClientBuilder;
+use miden_client::keystore::WebKeyStore;
+use miden_client::rpc::WebTonicRpcClient;
+use miden_client::store::memory::MemoryStore;
+use miden_client::{Client, Endpoint};
+use rand::RngCore;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+use crate::account_ops;
+use crate::keyring::KeyRing;
+
+type WasmClient = Client<WebTonicRpcClient>;
+
+fn to_js_error<E: std::fmt::Display>(err: E) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+fn parse_account_id(bech32: &str) -> Result<AccountId, JsValue> {
+    AccountId::from_bech32(bech32)
+        .map(|(_, account_id)| account_id)
+        .map_err(to_js_error)
+}
+
+/// A connected wallet/faucet session, holding the `Client` and keystore that
+/// every operation below shares. The JS caller creates one with
+/// [`WasmSession::connect`] and reuses it for the lifetime of the page, the
+/// way the native demo reuses one `Client` across its own `main`.
+#[wasm_bindgen]
+pub struct WasmSession {
+    client: WasmClient,
+    keystore: Arc<WebKeyStore>,
+}
+
+#[wasm_bindgen]
+impl WasmSession {
+    /// Connects to `rpc_url` and returns a session ready to create wallets
+    /// and faucets. `wasm-bindgen` constructors can't be async, so this is a
+    /// plain associated function rather than `#[wasm_bindgen(constructor)]`;
+    /// JS calls it as `WasmSession.connect(rpcUrl)`.
+    pub async fn connect(rpc_url: String) -> Result<WasmSession, JsValue> {
+        let endpoint = Endpoint::try_from(rpc_url.as_str()).map_err(to_js_error)?;
+        let rpc_client = Arc::new(WebTonicRpcClient::new(&endpoint, 10_000));
+        let keystore = Arc::new(WebKeyStore::new());
+
+        let client = ClientBuilder::new()
+            .rpc(rpc_client)
+            .store(MemoryStore::new())
+            .authenticator(keystore.clone())
+            .build()
+            .await
+            .map_err(to_js_error)?;
+
+        Ok(WasmSession { client, keystore })
+    }
+
+    /// Creates a new wallet account and returns its bech32 account ID.
+    #[wasm_bindgen(js_name = createWallet)]
+    pub async fn create_wallet(&mut self) -> Result<String, JsValue> {
+        let mut init_seed = [0_u8; 32];
+        self.client.rng().fill_bytes(&mut init_seed);
+        let key_pair = AuthSecretKey::new_falcon512_rpo();
+
+        let account = account_ops::build_wallet_account(init_seed, &key_pair).map_err(to_js_error)?;
+
+        self.client
+            .add_account(&account, false)
+            .await
+            .map_err(to_js_error)?;
+        self.keystore.add_key(&key_pair).map_err(to_js_error)?;
+
+        Ok(account.id().to_bech32(NetworkId::Testnet))
+    }
+
+    /// Deploys a new fungible faucet and returns its bech32 account ID.
+    #[wasm_bindgen(js_name = deployFaucet)]
+    pub async fn deploy_faucet(
+        &mut self,
+        symbol: String,
+        decimals: u8,
+        max_supply: u64,
+    ) -> Result<String, JsValue> {
+        let mut init_seed = [0_u8; 32];
+        self.client.rng().fill_bytes(&mut init_seed);
+        let key_pair = AuthSecretKey::new_falcon512_rpo();
+
+        let faucet = account_ops::build_faucet_account(init_seed, &key_pair, &symbol, decimals, max_supply)
+            .map_err(to_js_error)?;
+
+        self.client
+            .add_account(&faucet, false)
+            .await
+            .map_err(to_js_error)?;
+        self.keystore.add_key(&key_pair).map_err(to_js_error)?;
+
+        Ok(faucet.id().to_bech32(NetworkId::Testnet))
+    }
+
+    /// Mints `amount` tokens from `faucet_id` to `recipient_id`, returning the tx ID.
+    pub async fn mint(
+        &mut self,
+        faucet_id: String,
+        recipient_id: String,
+        amount: u64,
+    ) -> Result<String, JsValue> {
+        let faucet_id = parse_account_id(&faucet_id)?;
+        let recipient_id = parse_account_id(&recipient_id)?;
+        let transaction_request =
+            account_ops::build_mint_request(faucet_id, recipient_id, amount, self.client.rng())
+                .map_err(to_js_error)?;
+
+        let tx_id = self
+            .client
+            .submit_new_transaction(faucet_id, transaction_request)
+            .await
+            .map_err(to_js_error)?;
+
+        Ok(tx_id.to_string())
+    }
+
+    /// Consumes every consumable note for `account_id`, returning the tx ID.
+    #[wasm_bindgen(js_name = consumeNotes)]
+    pub async fn consume_notes(&mut self, account_id: String) -> Result<String, JsValue> {
+        let account_id = parse_account_id(&account_id)?;
+
+        self.client.sync_state().await.map_err(to_js_error)?;
+        let consumable_notes = self
+            .client
+            .get_consumable_notes(Some(account_id))
+            .await
+            .map_err(to_js_error)?;
+        let notes = consumable_notes
+            .into_iter()
+            .map(|(note, _)| note.try_into().map_err(to_js_error))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let transaction_request = account_ops::build_consume_notes_request(notes).map_err(to_js_error)?;
+
+        let tx_id = self
+            .client
+            .submit_new_transaction(account_id, transaction_request)
+            .await
+            .map_err(to_js_error)?;
+
+        Ok(tx_id.to_string())
+    }
+
+    /// Sends `amount` tokens of `faucet_id` from `sender_id` to `recipient_id` via P2ID.
+    #[wasm_bindgen(js_name = sendP2id)]
+    pub async fn send_p2id(
+        &mut self,
+        sender_id: String,
+        recipient_id: String,
+        faucet_id: String,
+        amount: u64,
+    ) -> Result<String, JsValue> {
+        let sender_id = parse_account_id(&sender_id)?;
+        let recipient_id = parse_account_id(&recipient_id)?;
+        let faucet_id = parse_account_id(&faucet_id)?;
+
+        let transaction_request = account_ops::build_send_p2id_request(
+            sender_id,
+            recipient_id,
+            faucet_id,
+            amount,
+            self.client.rng(),
+        )
+        .map_err(to_js_error)?;
+
+        let tx_id = self
+            .client
+            .submit_new_transaction(sender_id, transaction_request)
+            .await
+            .map_err(to_js_error)?;
+
+        Ok(tx_id.to_string())
+    }
+}